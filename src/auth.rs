@@ -0,0 +1,567 @@
+//! Credentials for authenticating requests to the Streaming API.
+//!
+//! The [`Builder`](crate::Builder) accepts any type implementing [`Authenticator`], which
+//! includes the OAuth 1.0a [`Token`] and the credential types defined in this module.
+
+use std::fmt::{self, Debug, Formatter};
+
+use bytes::Buf;
+use http::header::{HeaderValue, InvalidHeaderValue};
+use http::StatusCode;
+use http_body::Body;
+use oauth::serializer::Serializer;
+
+use crate::builder::RequestMethod;
+use crate::service::HttpService;
+use crate::util::{percent_encode, poll_fn, Hidden};
+use crate::{Credentials, Token};
+
+/// Credentials that can authorize requests to the Streaming API.
+pub trait Authenticator {
+    /// Returns the value of the `Authorization` header for a request.
+    ///
+    /// `uri` is the endpoint URI without the query part. `parameters` holds the parameters of
+    /// the request, including the query pairs of the endpoint URI, for schemes that sign them.
+    fn authorization(
+        &self,
+        method: &RequestMethod,
+        uri: &str,
+        parameters: &SortedPairs,
+    ) -> HeaderValue;
+}
+
+impl<A: Authenticator + ?Sized> Authenticator for &A {
+    fn authorization(
+        &self,
+        method: &RequestMethod,
+        uri: &str,
+        parameters: &SortedPairs,
+    ) -> HeaderValue {
+        (**self).authorization(method, uri, parameters)
+    }
+}
+
+impl<A: Authenticator + ?Sized> Authenticator for Box<A> {
+    fn authorization(
+        &self,
+        method: &RequestMethod,
+        uri: &str,
+        parameters: &SortedPairs,
+    ) -> HeaderValue {
+        (**self).authorization(method, uri, parameters)
+    }
+}
+
+/// The parameters of a request, percent-encoded and sorted as required for signing.
+///
+/// `oauth1-request` requires the parameters to be serialized in ascending order, and checks it
+/// only with a debug assertion, so a misplaced pair would silently break the signature
+/// in release builds. Collecting all the pairs from the various sources and sorting them
+/// keeps the order correct regardless of where the pairs come from.
+#[derive(Clone, Debug, Default)]
+pub struct SortedPairs {
+    /// Percent-encoded pairs sorted by key and then by value.
+    pairs: Vec<(String, String)>,
+}
+
+impl SortedPairs {
+    /// Sorts percent-encoded `pairs`.
+    pub(crate) fn new(mut pairs: Vec<(String, String)>) -> Self {
+        pairs.sort_unstable();
+        SortedPairs { pairs }
+    }
+
+    /// Returns an iterator over the percent-encoded key-value pairs, sorted by key and then
+    /// by value.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.pairs.iter().map(|(k, v)| (&**k, &**v))
+    }
+}
+
+impl oauth::Request for SortedPairs {
+    fn serialize<S: Serializer>(&self, mut serializer: S) -> S::Output {
+        // The `oauth_*` parameters need to be placed in order among the other pairs as well.
+        let oauth = [
+            ("oauth_callback", S::serialize_oauth_callback as fn(&mut S)),
+            ("oauth_consumer_key", S::serialize_oauth_consumer_key),
+            ("oauth_nonce", S::serialize_oauth_nonce),
+            (
+                "oauth_signature_method",
+                S::serialize_oauth_signature_method,
+            ),
+            ("oauth_timestamp", S::serialize_oauth_timestamp),
+            ("oauth_token", S::serialize_oauth_token),
+            ("oauth_verifier", S::serialize_oauth_verifier),
+            ("oauth_version", S::serialize_oauth_version),
+        ];
+        let mut oauth = &oauth[..];
+
+        for (k, v) in &self.pairs {
+            while let Some((&(key, serialize), rest)) = oauth.split_first() {
+                if key >= k.as_str() {
+                    break;
+                }
+                serialize(&mut serializer);
+                oauth = rest;
+            }
+            serializer.serialize_parameter_encoded(k, v);
+        }
+        for &(_, serialize) in oauth {
+            serialize(&mut serializer);
+        }
+
+        serializer.end()
+    }
+}
+
+impl<C: AsRef<str>, T: AsRef<str>> Authenticator for Token<C, T> {
+    fn authorization(
+        &self,
+        method: &RequestMethod,
+        uri: &str,
+        parameters: &SortedPairs,
+    ) -> HeaderValue {
+        let token = self.as_ref();
+        let mut oauth = oauth::Builder::new(token.client, oauth::HmacSha1);
+        oauth.token(token.token);
+        let authorization = oauth.build(method.as_ref(), uri, parameters);
+        // The header only contains percent-encoded values, so it cannot be invalid.
+        let mut value = HeaderValue::from_str(&authorization).unwrap();
+        value.set_sensitive(true);
+        value
+    }
+}
+
+/// A username and password for HTTP Basic authentication.
+///
+/// This is used by the enterprise (Gnip) streaming endpoints, which do not support OAuth.
+/// Since the enterprise endpoints are specific to each account, you need to set the endpoint
+/// with [`Builder::endpoint`](crate::Builder::endpoint) when using `BasicAuth`.
+///
+/// # Example
+///
+/// ```no_run
+/// use twitter_stream::auth::BasicAuth;
+/// use twitter_stream::builder::{RequestMethod, Uri};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let auth = BasicAuth::new("username", "password");
+/// let endpoint = Uri::from_static(
+///     "https://gnip-stream.twitter.com/stream/powertrack/accounts/example/publishers/twitter/prod.json",
+/// );
+///
+/// let stream = twitter_stream::Builder::new(auth)
+///     .endpoint((RequestMethod::GET, endpoint))
+///     .listen()
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct BasicAuth<U = String, P = String> {
+    /// The username.
+    pub username: U,
+    /// The password.
+    pub password: P,
+}
+
+impl<U: AsRef<str>, P: AsRef<str>> BasicAuth<U, P> {
+    /// Creates a new `BasicAuth`.
+    pub fn new(username: U, password: P) -> Self {
+        BasicAuth { username, password }
+    }
+
+    /// Borrows the username and password.
+    pub fn as_ref(&self) -> BasicAuth<&str, &str> {
+        BasicAuth::new(self.username.as_ref(), self.password.as_ref())
+    }
+}
+
+impl<U: AsRef<str>, P: AsRef<str>> Authenticator for BasicAuth<U, P> {
+    fn authorization(&self, _: &RequestMethod, _: &str, _: &SortedPairs) -> HeaderValue {
+        let credentials = format!("{}:{}", self.username.as_ref(), self.password.as_ref());
+        let value = format!("Basic {}", base64::encode(credentials));
+        // Base64 output is always a valid header value.
+        let mut value = HeaderValue::from_str(&value).unwrap();
+        value.set_sensitive(true);
+        value
+    }
+}
+
+/// An OAuth 2.0 Bearer token for app-only authentication.
+///
+/// Some endpoints, like the v2 streaming endpoints, only accept app-only authentication. You
+/// need to set the endpoint with [`Builder::endpoint`](crate::Builder::endpoint) when using
+/// `BearerToken`.
+///
+/// # Example
+///
+/// ```no_run
+/// use twitter_stream::auth::BearerToken;
+/// use twitter_stream::builder::{RequestMethod, Uri};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let token = BearerToken::new("bearer_token").unwrap();
+/// let endpoint = Uri::from_static("https://api.twitter.com/2/tweets/sample/stream");
+///
+/// let stream = twitter_stream::Builder::new(token)
+///     .endpoint((RequestMethod::GET, endpoint))
+///     .listen()
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BearerToken {
+    /// The value of the `Authorization` header, which is validated on construction.
+    authorization: HeaderValue,
+}
+
+impl BearerToken {
+    /// Creates a new `BearerToken`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `token` contains a character that is not allowed in a header value,
+    /// like a newline.
+    pub fn new(token: impl AsRef<str>) -> Result<Self, InvalidHeaderValue> {
+        let authorization = format!("Bearer {}", token.as_ref());
+        let mut authorization = HeaderValue::from_str(&authorization)?;
+        authorization.set_sensitive(true);
+        Ok(BearerToken { authorization })
+    }
+}
+
+impl BearerToken {
+    /// Obtains a Bearer token for the application identified by `client` from the
+    /// [`POST oauth2/token`][1] endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the TLS backend cannot be initialized, as [`hyper_tls::HttpsConnector::new`]
+    /// does. Use [`obtain_with_client`](BearerToken::obtain_with_client) with a client of your own
+    /// to avoid this.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use twitter_stream::auth::BearerToken;
+    /// use twitter_stream::Credentials;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let client = Credentials::new("consumer_key", "consumer_secret");
+    /// let token = BearerToken::obtain(&client).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [1]: https://developer.twitter.com/en/docs/authentication/api-reference/token
+    #[cfg(feature = "hyper")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
+    pub async fn obtain<C: AsRef<str>>(
+        client: &Credentials<C>,
+    ) -> Result<Self, ObtainError<hyper_pkg::Error>> {
+        let conn = hyper_tls::HttpsConnector::new();
+        let http = hyper_pkg::Client::builder().build::<_, hyper_pkg::Body>(conn);
+        BearerToken::obtain_with_client(client, http).await
+    }
+
+    /// Obtains a Bearer token for the application identified by `client`, using `http` to make
+    /// the request.
+    ///
+    /// Unlike [`Builder::listen_with_client`](crate::Builder::listen_with_client), this drives
+    /// `http` to readiness before calling it.
+    pub async fn obtain_with_client<C, S, B, R>(
+        client: &Credentials<C>,
+        mut http: S,
+    ) -> Result<Self, ObtainError<S::Error>>
+    where
+        C: AsRef<str>,
+        S: HttpService<B, Response = http::Response<R>>,
+        B: From<Vec<u8>>,
+        R: Body,
+        R::Error: Into<S::Error>,
+    {
+        use http::header::{AUTHORIZATION, CONTENT_TYPE};
+
+        const OAUTH2_TOKEN: &str = "https://api.twitter.com/oauth2/token";
+
+        // The credentials need to be URL-encoded before being encoded in Base64.
+        let auth = BasicAuth::new(
+            percent_encode(client.identifier()),
+            percent_encode(client.secret()),
+        );
+        let req = http::Request::post(OAUTH2_TOKEN)
+            .header(
+                AUTHORIZATION,
+                auth.authorization(&RequestMethod::POST, OAUTH2_TOKEN, &SortedPairs::default()),
+            )
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/x-www-form-urlencoded;charset=UTF-8"),
+            )
+            .body(b"grant_type=client_credentials".to_vec())
+            .unwrap();
+
+        poll_fn(|cx| http.poll_ready(cx))
+            .await
+            .map_err(ObtainError::Service)?;
+        let res = http
+            .call(req.map(Into::into))
+            .await
+            .map_err(ObtainError::Service)?;
+        if !res.status().is_success() {
+            return Err(ObtainError::Http(res.status()));
+        }
+
+        let mut body = Box::pin(res.into_body());
+        let mut buf = Vec::new();
+        while let Some(data) = poll_fn(|cx| body.as_mut().poll_data(cx)).await {
+            let data = data.map_err(|e| ObtainError::Service(e.into()))?;
+            buf.extend_from_slice(data.chunk());
+        }
+        std::str::from_utf8(&buf)
+            .ok()
+            .and_then(access_token)
+            .and_then(|token| BearerToken::new(token).ok())
+            .ok_or(ObtainError::UnexpectedResponse)
+    }
+}
+
+/// An error that occurred while obtaining a Bearer token with
+/// [`BearerToken::obtain_with_client`].
+#[derive(Debug)]
+pub enum ObtainError<E = Box<dyn std::error::Error + Send + Sync>> {
+    /// An HTTP error from the endpoint.
+    Http(StatusCode),
+    /// Error from the HTTP client while sending the request or reading the response.
+    Service(E),
+    /// The response did not contain a valid Bearer token.
+    UnexpectedResponse,
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ObtainError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            ObtainError::Service(ref e) => Some(e),
+            ObtainError::Http(_) | ObtainError::UnexpectedResponse => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ObtainError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            ObtainError::Http(ref code) => write!(f, "HTTP status code: {}", code),
+            ObtainError::Service(ref e) => write!(f, "HTTP client error: {}", e),
+            ObtainError::UnexpectedResponse => f.write_str("no Bearer token in the response"),
+        }
+    }
+}
+
+impl Authenticator for BearerToken {
+    fn authorization(&self, _: &RequestMethod, _: &str, _: &SortedPairs) -> HeaderValue {
+        self.authorization.clone()
+    }
+}
+
+/// Extracts the `access_token` of a `bearer` token from the JSON response of `POST oauth2/token`.
+///
+/// The response is a flat JSON object. The token is percent-encoded, so this does not need to
+/// handle escape sequences in it, and a token containing one is rejected.
+fn access_token(body: &str) -> Option<&str> {
+    let mut token_type = None;
+    let mut access_token = None;
+
+    let mut rest = body.trim_start().strip_prefix('{')?.trim_start();
+    if let Some(r) = rest.strip_prefix('}') {
+        rest = r;
+    } else {
+        loop {
+            let (key, r) = json_string(rest)?;
+            let r = r.trim_start().strip_prefix(':')?.trim_start();
+            let (value, r) = if r.starts_with('"') {
+                let (value, r) = json_string(r)?;
+                (Some(value), r)
+            } else {
+                (None, skip_json_scalar(r)?)
+            };
+            match key {
+                "token_type" => token_type = value,
+                "access_token" => access_token = value,
+                _ => {}
+            }
+
+            let r = r.trim_start();
+            if let Some(r) = r.strip_prefix(',') {
+                rest = r.trim_start();
+            } else {
+                rest = r.strip_prefix('}')?;
+                break;
+            }
+        }
+    }
+
+    if !rest.trim().is_empty() || !token_type?.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+    let token = access_token?;
+    if token.is_empty() || token.contains('\\') {
+        return None;
+    }
+    Some(token)
+}
+
+/// Splits a JSON string literal at the start of `s` into its contents, which are not unescaped,
+/// and the rest.
+fn json_string(s: &str) -> Option<(&str, &str)> {
+    let s = s.strip_prefix('"')?;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some((&s[..i], &s[i + 1..])),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Skips a JSON number, `true`, `false` or `null` at the start of `s`.
+fn skip_json_scalar(s: &str) -> Option<&str> {
+    let end = s.find(|c: char| c == ',' || c == '}' || c.is_whitespace())?;
+    if end == 0 || matches!(s.as_bytes()[0], b'"' | b'{' | b'[') {
+        return None;
+    }
+    Some(&s[end..])
+}
+
+impl<U: Debug, P> Debug for BasicAuth<U, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicAuth")
+            .field("username", &self.username)
+            .field("password", &Hidden)
+            .finish()
+    }
+}
+
+impl Debug for BearerToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BearerToken")
+            .field("token", &Hidden)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_auth() {
+        // Example taken from RFC 7617.
+        let auth = BasicAuth::new("Aladdin", "open sesame");
+        assert_eq!(
+            auth.authorization(
+                &RequestMethod::GET,
+                "https://example.com/",
+                &SortedPairs::default()
+            ),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==",
+        );
+        assert_eq!(
+            format!("{:?}", auth),
+            r#"BasicAuth { username: "Aladdin", password: <hidden> }"#,
+        );
+
+        let auth: Box<dyn Authenticator> = Box::new(auth);
+        assert_eq!(
+            auth.authorization(
+                &RequestMethod::GET,
+                "https://example.com/",
+                &SortedPairs::default()
+            ),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==",
+        );
+    }
+
+    #[test]
+    fn bearer_token() {
+        let token = BearerToken::new("AAAA%2FAAA%3DAAAAAAAA").unwrap();
+        assert_eq!(
+            token.authorization(
+                &RequestMethod::GET,
+                "https://example.com/",
+                &SortedPairs::default()
+            ),
+            "Bearer AAAA%2FAAA%3DAAAAAAAA",
+        );
+        assert_eq!(format!("{:?}", token), "BearerToken { token: <hidden> }");
+
+        assert!(BearerToken::new("AAAA\nAAAA").is_err());
+    }
+
+    #[test]
+    fn obtain_with_client() {
+        use std::convert::Infallible;
+
+        use bytes::Bytes;
+        use http::header::AUTHORIZATION;
+
+        let client = Credentials::new(
+            "xvz1evFS4wEEPTGEFPHBog",
+            "L8qq9PZyRg6ieKGEKhZolGC0vJWLw8iEJ88DRdyOg",
+        );
+        let http = tower::service_fn(|req: http::Request<Vec<u8>>| {
+            // Example taken from the documentation of `POST oauth2/token`.
+            assert_eq!(
+                req.headers()[AUTHORIZATION],
+                "Basic eHZ6MWV2RlM0d0VFUFRHRUZQSEJvZzpMOHFxOVBaeVJnNmllS0dFS2hab2xHQzB2SldMdzhpRUo4OERSZHlPZw==",
+            );
+            assert_eq!(req.body(), b"grant_type=client_credentials");
+            let body = r#"{"token_type":"bearer","access_token":"AAAA%2FAAA%3DAAAAAAAA"}"#;
+            futures::future::ok::<_, Infallible>(http::Response::new(http_body::Full::new(
+                Bytes::from_static(body.as_bytes()),
+            )))
+        });
+
+        let token = futures::executor::block_on(BearerToken::obtain_with_client(&client, http));
+        assert_eq!(
+            token.unwrap().authorization(
+                &RequestMethod::GET,
+                "https://example.com/",
+                &SortedPairs::default()
+            ),
+            "Bearer AAAA%2FAAA%3DAAAAAAAA",
+        );
+    }
+
+    #[test]
+    fn parse_access_token() {
+        // Example taken from the documentation of `POST oauth2/token`.
+        let body = r#"{"token_type":"bearer","access_token":"AAAA%2FAAA%3DAAAAAAAA"}"#;
+        assert_eq!(access_token(body), Some("AAAA%2FAAA%3DAAAAAAAA"));
+        let body = r#"{ "access_token" : "AAAA", "token_type" : "bearer" }"#;
+        assert_eq!(access_token(body), Some("AAAA"));
+        let body = r#"{"expires_in":3600,"token_type":"Bearer","access_token":"AAAA"}"#;
+        assert_eq!(access_token(body), Some("AAAA"));
+
+        assert_eq!(access_token(r#"{"token_type":"bearer"}"#), None);
+        assert_eq!(access_token(r#"{"access_token":"AAAA"}"#), None);
+        let body = r#"{"token_type":"mac","access_token":"AAAA"}"#;
+        assert_eq!(access_token(body), None);
+        let body = r#"{"token_type":"bearer","access_token":"AA\u0041"}"#;
+        assert_eq!(access_token(body), None);
+        let body = r#"{"token_type":"bearer","access_token":42}"#;
+        assert_eq!(access_token(body), None);
+        // `access_token` only counts as a key, not as part of another value.
+        let body = r#"{"token_type":"bearer","note":"\"access_token\":\"AAAA\""}"#;
+        assert_eq!(access_token(body), None);
+        let body = r#"{"token_type":"bearer","error":{"access_token":"AAAA"}}"#;
+        assert_eq!(access_token(body), None);
+        let body = r#"{"token_type":"bearer","access_token":"AAAA"} trailing"#;
+        assert_eq!(access_token(body), None);
+    }
+}
@@ -47,24 +47,28 @@ pub use http::Uri;
 pub use bounding_box::BoundingBox;
 
 use std::borrow::Cow;
-use std::fmt::{self, Formatter};
+use std::fmt::{self, Display, Formatter};
 
 use http::header::{HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
 use http::Request;
+use oauth::serializer::Serializer;
 use slice_of_array::SliceFlatExt;
 
+use crate::auth::{Authenticator, SortedPairs};
 use crate::service::HttpService;
-use crate::util::fmt_join;
+use crate::util::{fmt_join, percent_decode, percent_encode, Hidden};
 use crate::{FutureTwitterStream, Token};
 
 /// A builder for [`TwitterStream`](crate::TwitterStream).
 ///
 /// See the [`builder`][crate::builder] module documentation for details.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Builder<'a, T = Token> {
     token: T,
     endpoint: Option<(RequestMethod, Uri)>,
     parameters: Parameters<'a>,
+    #[cfg(feature = "hyper")]
+    tcp_nodelay: bool,
 }
 
 /// Parameters to the Streaming API.
@@ -84,6 +88,10 @@ struct Parameters<'a> {
     locations: Cow<'a, [BoundingBox]>,
     #[oauth1(encoded)]
     count: Option<i32>,
+    tweet_mode: Option<TweetMode>,
+    /// Parameters without a dedicated setter, in insertion order.
+    #[oauth1(skip)]
+    custom: Vec<(Cow<'a, str>, Cow<'a, str>)>,
 }
 
 str_enum! {
@@ -101,23 +109,25 @@ str_enum! {
     }
 }
 
+str_enum! {
+    /// Represents the `tweet_mode` parameter in API requests.
+    ///
+    /// See the [Twitter Developer Documentation][1] for more information.
+    ///
+    /// [1]: https://developer.twitter.com/en/docs/twitter-api/v1/data-dictionary/guides/extended-tweets
+    #[derive(Clone, Copy, Debug, PartialEq, Hash, Eq)]
+    pub enum TweetMode {
+        /// `"compat"`
+        Compat = "compat",
+        /// `"extended"`
+        Extended = "extended",
+    }
+}
+
 const FILTER: &str = "https://stream.twitter.com/1.1/statuses/filter.json";
 const SAMPLE: &str = "https://stream.twitter.com/1.1/statuses/sample.json";
 
-impl<'a, C, A> Builder<'a, Token<C, A>>
-where
-    C: AsRef<str>,
-    A: AsRef<str>,
-{
-    /// Creates a builder.
-    pub fn new(token: Token<C, A>) -> Self {
-        Builder {
-            token,
-            endpoint: None,
-            parameters: Parameters::default(),
-        }
-    }
-
+impl<'a, T: Authenticator> Builder<'a, T> {
     /// Start listening on the Streaming API endpoint, returning a `Future` which resolves
     /// to a `Stream` yielding JSON messages from the API.
     ///
@@ -127,8 +137,51 @@ where
     #[cfg(feature = "hyper")]
     #[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
     pub fn listen(&self) -> crate::hyper::FutureTwitterStream {
-        let conn = hyper_tls::HttpsConnector::new();
-        self.listen_with_client(hyper_pkg::Client::builder().build::<_, hyper_pkg::Body>(conn))
+        self.listen_with_client_builder(&hyper_pkg::Client::builder())
+    }
+
+    /// Same as [`listen`](Builder::listen) except that it builds the HTTP client with
+    /// `client_builder`.
+    ///
+    /// This is useful for tuning the client, like setting the pool idle timeout,
+    /// the read buffer size or the executor to spawn background tasks on.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the underlying HTTPS connector failed to initialize.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// # use hyper_pkg as hyper;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let token = twitter_stream::Token::from_parts("", "", "", "");
+    /// let mut client_builder = hyper::Client::builder();
+    /// client_builder
+    ///     .pool_idle_timeout(Duration::from_secs(30))
+    ///     .http1_read_buf_exact_size(64 * 1024);
+    ///
+    /// let stream = twitter_stream::Builder::new(token)
+    ///     .track("@Twitter")
+    ///     .listen_with_client_builder(&client_builder)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "hyper")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
+    pub fn listen_with_client_builder(
+        &self,
+        client_builder: &hyper_pkg::client::Builder,
+    ) -> crate::hyper::FutureTwitterStream {
+        let mut http = hyper_pkg::client::HttpConnector::new();
+        http.enforce_http(false);
+        http.set_nodelay(self.tcp_nodelay);
+        let conn = hyper_tls::HttpsConnector::new_with_connector(http);
+        self.listen_with_client(client_builder.build::<_, hyper_pkg::Body>(conn))
     }
 
     /// Same as [`listen`](Builder::listen) except that it uses `client` to make HTTP request
@@ -150,7 +203,7 @@ where
     /// # let mut client = hyper_pkg::Client::new();
     /// # let token = twitter_stream::Token::from_parts("", "", "", "");
     /// let stream = twitter_stream::Builder::new(token)
-    ///     .listen_with_client(client.ready_and().await?)
+    ///     .listen_with_client(client.ready().await?)
     ///     .await
     ///     .unwrap();
     /// # Ok(())
@@ -161,32 +214,74 @@ where
         S: HttpService<B>,
         B: From<Vec<u8>>,
     {
-        let req = prepare_request(
-            self.endpoint.as_ref(),
-            self.token.as_ref(),
-            &self.parameters,
-        );
+        let req = prepare_request(self.endpoint.as_ref(), &self.token, &self.parameters);
         let response = client.call(req.map(Into::into));
 
         FutureTwitterStream { response }
     }
 }
 
-impl<'a, C, A> Builder<'a, Token<C, A>> {
+impl<'a, T> Builder<'a, T> {
+    /// Creates a builder.
+    ///
+    /// `token` is any [`Authenticator`], like a [`Token`] for OAuth 1.0a.
+    pub fn new(token: T) -> Self {
+        Builder {
+            token,
+            endpoint: None,
+            parameters: Parameters::default(),
+            #[cfg(feature = "hyper")]
+            tcp_nodelay: true,
+        }
+    }
+
     /// Set the API endpoint URI to be connected.
     ///
     /// This overrides the default behavior of automatically determining the endpoint to use.
+    ///
+    /// The query part of the URI, if any, is signed along with the other parameters.
+    /// Its percent-encoding is normalized before signing, so `+` and lowercase escapes like
+    /// `%2c` are accepted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the URI is not an absolute URI, i.e. if it lacks a scheme or an authority
+    /// (e.g. `stream.twitter.com` or `/1.1/statuses/sample.json`).
     pub fn endpoint(&mut self, endpoint: impl Into<Option<(RequestMethod, Uri)>>) -> &mut Self {
-        self.endpoint = endpoint.into();
+        let endpoint = endpoint.into();
+        if let Some((_, ref uri)) = endpoint {
+            assert!(
+                uri.scheme().is_some() && uri.authority().is_some(),
+                "endpoint must be an absolute URI: {}",
+                uri,
+            );
+        }
+        self.endpoint = endpoint;
         self
     }
 
     /// Reset the token to be used to log into Twitter.
-    pub fn token(&mut self, token: Token<C, A>) -> &mut Self {
+    pub fn token(&mut self, token: T) -> &mut Self {
         self.token = token;
         self
     }
 
+    /// Set whether to set the `TCP_NODELAY` option on the connection made by
+    /// [`listen`](Builder::listen) and [`listen_with_client_builder`](Builder::listen_with_client_builder).
+    ///
+    /// The default is `true`, which disables Nagle's algorithm on the socket so that the
+    /// request is sent without waiting to coalesce it with further writes. The option only
+    /// affects data written by the client, so it has no effect on how the stream is received.
+    ///
+    /// This has no effect on [`listen_with_client`](Builder::listen_with_client), in which case
+    /// the option should be set on the connector of `client`.
+    #[cfg(feature = "hyper")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
+    pub fn tcp_nodelay(&mut self, tcp_nodelay: bool) -> &mut Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
     /// Set whether to receive messages when in danger of being disconnected.
     ///
     /// See the [Twitter Developer Documentation][1] for more information.
@@ -245,12 +340,46 @@ impl<'a, C, A> Builder<'a, Token<C, A>> {
         self
     }
 
+    /// Set a list of phrases to filter Tweets by.
+    ///
+    /// This is a shorthand for [`track`](Builder::track) that joins the phrases with commas.
+    /// The phrases are passed as is, and since the Streaming API splits the list at commas,
+    /// a phrase containing a comma is treated as separate phrases (`"a,b"` is the same as
+    /// `"a"` and `"b"`).
+    ///
+    /// Setting an empty list will unset this parameter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use twitter_stream::{Builder, Token};
+    ///
+    /// # let token = Token::from_parts("", "", "", "");
+    /// let keywords = vec!["rust".to_owned(), "rustlang".to_owned()];
+    /// Builder::new(token).track_phrases(&keywords);
+    /// ```
+    pub fn track_phrases<I>(&mut self, phrases: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut track = String::new();
+        for phrase in phrases {
+            if !track.is_empty() {
+                track.push(',');
+            }
+            track.push_str(phrase.as_ref());
+        }
+        self.track(track)
+    }
+
     /// Set a list of bounding boxes to filter Tweets by.
     ///
     /// Setting an empty slice will unset this parameter.
     ///
     /// See [`BoundingBox`](struct.BoundingBox.html) and
     /// the [Twitter Developer Documentation][1] for more information.
+    /// [`BoundingBox::is_valid`] can be used to check the coordinates beforehand.
     ///
     /// [1]: https://developer.twitter.com/en/docs/tweets/filter-realtime/guides/basic-stream-parameters#locations
     pub fn locations(&mut self, locations: impl Into<Cow<'a, [BoundingBox]>>) -> &mut Self {
@@ -268,6 +397,60 @@ impl<'a, C, A> Builder<'a, Token<C, A>> {
         self.parameters.count = count.into();
         self
     }
+
+    /// Set the `tweet_mode` parameter.
+    ///
+    /// The streaming endpoints always deliver Tweets in compatibility mode regardless of this
+    /// parameter: `text` may be truncated, and the full text of a longer Tweet is found in its
+    /// `extended_tweet` object. `TweetMode::Extended` only replaces `text` with `full_text` on
+    /// the REST endpoints, which can be set with [`endpoint`](Builder::endpoint).
+    ///
+    /// See the [Twitter Developer Documentation][1] for more information.
+    ///
+    /// [1]: https://developer.twitter.com/en/docs/twitter-api/v1/data-dictionary/guides/extended-tweets
+    pub fn tweet_mode(&mut self, tweet_mode: impl Into<Option<TweetMode>>) -> &mut Self {
+        self.parameters.tweet_mode = tweet_mode.into();
+        self
+    }
+
+    /// Add an arbitrary parameter to the request.
+    ///
+    /// This is for parameters that do not have a dedicated setter, like ones required by
+    /// a custom [`endpoint`](Builder::endpoint). The parameter is signed and sent along with
+    /// the other parameters. Adding a parameter that has a dedicated setter will send
+    /// the parameter twice.
+    ///
+    /// Both `key` and `value` are percent-encoded when sending the request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use twitter_stream::{Builder, Token};
+    ///
+    /// # let token = Token::from_parts("", "", "", "");
+    /// Builder::new(token).track("@Twitter").parameter("delimited", "length");
+    /// ```
+    pub fn parameter(
+        &mut self,
+        key: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, str>>,
+    ) -> &mut Self {
+        self.parameters.custom.push((key.into(), value.into()));
+        self
+    }
+}
+
+impl<'a, T> fmt::Debug for Builder<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Keep the credentials out of logs.
+        let mut f = f.debug_struct("Builder");
+        f.field("token", &Hidden)
+            .field("endpoint", &self.endpoint)
+            .field("parameters", &self.parameters);
+        #[cfg(feature = "hyper")]
+        f.field("tcp_nodelay", &self.tcp_nodelay);
+        f.finish()
+    }
 }
 
 impl std::default::Default for FilterLevel {
@@ -282,13 +465,19 @@ impl std::fmt::Display for FilterLevel {
     }
 }
 
-fn prepare_request(
+impl std::fmt::Display for TweetMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        AsRef::<str>::as_ref(self).fmt(f)
+    }
+}
+
+fn prepare_request<A: Authenticator>(
     endpoint: Option<&(RequestMethod, Uri)>,
-    token: Token<&str, &str>,
+    token: &A,
     parameters: &Parameters<'_>,
 ) -> http::Request<Vec<u8>> {
     let uri;
-    let (method, endpoint) = if let Some(&(ref method, ref endpoint)) = endpoint {
+    let (method, endpoint) = if let Some((method, endpoint)) = endpoint {
         (method, endpoint)
     } else if parameters.follow.is_empty()
         && parameters.track.is_empty()
@@ -303,12 +492,15 @@ fn prepare_request(
 
     let req = Request::builder().method(method.clone());
 
-    let mut oauth = oauth::Builder::new(token.client.as_ref(), oauth::HmacSha1);
-    oauth.token(token.token.as_ref());
+    // The query part of the endpoint URI needs to be signed along with the parameters,
+    // but `oauth1-request` expects a URI without a query part.
+    let endpoint_str = endpoint.to_string();
+    let (base, query) = split_query(&endpoint_str);
+    let signed = sorted_pairs(parameters, query);
+    let authorization = token.authorization(method, base, &signed);
 
     if RequestMethod::POST == method {
-        let authorization = oauth.post(endpoint, parameters);
-        let data = oauth::to_form_urlencoded(parameters);
+        let data = oauth::to_form_urlencoded(&sorted_pairs(parameters, ""));
 
         req.uri(endpoint.clone())
             .header(AUTHORIZATION, authorization)
@@ -320,8 +512,7 @@ fn prepare_request(
             .body(data.into_bytes())
             .unwrap()
     } else {
-        let authorization = oauth.build(method.as_ref(), endpoint, parameters);
-        let uri = oauth::to_uri_query(endpoint.to_string(), parameters);
+        let uri = oauth::to_uri_query(base.to_owned(), &signed);
 
         req.uri(uri)
             .header(AUTHORIZATION, authorization)
@@ -330,6 +521,66 @@ fn prepare_request(
     }
 }
 
+/// Splits a URI into the part preceding the query and the query part.
+fn split_query(uri: &str) -> (&str, &str) {
+    if let Some(i) = uri.find('?') {
+        (&uri[..i], &uri[i + 1..])
+    } else {
+        (uri, "")
+    }
+}
+
+/// A `Serializer` that collects the parameters of a `Request` as percent-encoded pairs.
+struct Collect(Vec<(String, String)>);
+
+/// Collects the `parameters`, including the custom ones, and the percent-encoded `query`.
+fn sorted_pairs(parameters: &Parameters<'_>, query: &str) -> SortedPairs {
+    let mut pairs = oauth::Request::serialize(parameters, Collect(Vec::new()));
+    pairs.extend(
+        query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (k, v) = if let Some(i) = pair.find('=') {
+                    (&pair[..i], &pair[i + 1..])
+                } else {
+                    (pair, "")
+                };
+                // Normalize the encoding so that the pairs are signed the same way as
+                // Twitter reconstructs them.
+                let k = percent_encode(percent_decode(k));
+                let v = percent_encode(percent_decode(v));
+                (k, v)
+            }),
+    );
+    pairs.extend(
+        parameters
+            .custom
+            .iter()
+            .map(|(k, v)| (percent_encode(k.as_bytes()), percent_encode(v.as_bytes()))),
+    );
+
+    SortedPairs::new(pairs)
+}
+
+impl Serializer for Collect {
+    type Output = Vec<(String, String)>;
+
+    fn serialize_parameter<V: Display>(&mut self, k: &str, v: V) {
+        self.0.push((k.to_owned(), percent_encode(v.to_string())));
+    }
+
+    fn serialize_parameter_encoded<V: Display>(&mut self, k: &str, v: V) {
+        self.0.push((k.to_owned(), v.to_string()));
+    }
+
+    oauth::serializer::skip_serialize_oauth_parameters!();
+
+    fn end(self) -> Self::Output {
+        self.0
+    }
+}
+
 const COMMA: &str = "%2C";
 
 fn fmt_follow(ids: &[u64], f: &mut Formatter<'_>) -> fmt::Result {
@@ -344,3 +595,241 @@ fn fmt_locations(locs: &[BoundingBox], f: &mut Formatter<'_>) -> fmt::Result {
 fn not(p: &bool) -> bool {
     !p
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_query() {
+        let token = Token::from_parts("", "", "", "");
+
+        let endpoint = (
+            RequestMethod::GET,
+            Uri::from_static(
+                "https://stream.twitter.com/1.1/statuses/sample.json?delimited=length",
+            ),
+        );
+        let mut parameters = Parameters {
+            stall_warnings: true,
+            ..Parameters::default()
+        };
+        let req = prepare_request(Some(&endpoint), &token, &parameters);
+        assert_eq!(
+            req.uri(),
+            "https://stream.twitter.com/1.1/statuses/sample.json?delimited=length&stall_warnings=true",
+        );
+
+        let endpoint = (
+            RequestMethod::POST,
+            Uri::from_static(
+                "https://stream.twitter.com/1.1/statuses/filter.json?delimited=length",
+            ),
+        );
+        parameters.track = "@Twitter".into();
+        let req = prepare_request(Some(&endpoint), &token, &parameters);
+        assert_eq!(req.uri(), &endpoint.1);
+        assert_eq!(req.body(), b"stall_warnings=true&track=%40Twitter");
+
+        parameters.tweet_mode = Some(TweetMode::Extended);
+        let req = prepare_request(Some(&endpoint), &token, &parameters);
+        assert_eq!(
+            req.body(),
+            b"stall_warnings=true&track=%40Twitter&tweet_mode=extended",
+        );
+
+        let endpoint = (
+            RequestMethod::GET,
+            Uri::from_static("https://api.twitter.com/1.1/search/tweets.json?q=a+b&z=%2c&~=!"),
+        );
+        let req = prepare_request(Some(&endpoint), &token, &Parameters::default());
+        assert_eq!(
+            req.uri(),
+            "https://api.twitter.com/1.1/search/tweets.json?q=a%20b&z=%2C&~=%21",
+        );
+
+        let endpoint = (RequestMethod::GET, Uri::from_static("https://example.com"));
+        let req = prepare_request(Some(&endpoint), &token, &parameters);
+        assert_eq!(
+            req.uri(),
+            "https://example.com/?stall_warnings=true&track=%40Twitter&tweet_mode=extended",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "endpoint must be an absolute URI")]
+    fn endpoint_authority_form() {
+        let token = Token::from_parts("", "", "", "");
+        Builder::new(token)
+            .endpoint((RequestMethod::GET, Uri::from_static("stream.twitter.com")))
+            .track("x");
+    }
+
+    #[test]
+    fn custom_parameters() {
+        let token = Token::from_parts("", "", "", "");
+
+        let mut builder = Builder::new(token);
+        builder
+            .track("@Twitter")
+            .parameter("with", "followings")
+            .parameter("delimited", "length");
+        let req = prepare_request(None, &builder.token, &builder.parameters);
+        assert_eq!(
+            req.body(),
+            b"delimited=length&track=%40Twitter&with=followings",
+        );
+
+        builder.endpoint((
+            RequestMethod::GET,
+            Uri::from_static("https://stream.twitter.com/1.1/statuses/sample.json?a=b"),
+        ));
+        builder.parameter("replies", "all users");
+        let req = prepare_request(
+            builder.endpoint.as_ref(),
+            &builder.token,
+            &builder.parameters,
+        );
+        assert_eq!(
+            req.uri(),
+            "https://stream.twitter.com/1.1/statuses/sample.json\
+             ?a=b&delimited=length&replies=all%20users&track=%40Twitter&with=followings",
+        );
+    }
+
+    #[test]
+    fn custom_parameter_reserved_key() {
+        let token = Token::from_parts("", "", "", "");
+
+        let mut builder = Builder::new(token);
+        builder.track("rust").parameter("a&b", "c=d");
+        let req = prepare_request(None, &builder.token, &builder.parameters);
+        assert_eq!(req.body(), b"a%26b=c%3Dd&track=rust");
+
+        builder.track("").endpoint((
+            RequestMethod::GET,
+            Uri::from_static("https://stream.twitter.com/1.1/statuses/sample.json"),
+        ));
+        builder.parameter("a b", "c");
+        let req = prepare_request(
+            builder.endpoint.as_ref(),
+            &builder.token,
+            &builder.parameters,
+        );
+        assert_eq!(
+            req.uri(),
+            "https://stream.twitter.com/1.1/statuses/sample.json?a%20b=c&a%26b=c%3Dd",
+        );
+    }
+
+    #[test]
+    fn sorted_pairs_signature() {
+        use std::collections::BTreeSet;
+        use std::num::NonZeroU64;
+
+        let token = Token::from_parts("ck", "cs", "ak", "as");
+        let mut oauth = oauth::Builder::new(token.client.as_ref(), oauth::HmacSha1);
+        oauth
+            .token(token.token.as_ref())
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1_234_567_890));
+
+        let parameters = Parameters {
+            stall_warnings: true,
+            filter_level: Some(FilterLevel::Low),
+            language: "en".into(),
+            follow: vec![12, 783214].into(),
+            track: "@Twitter,rust lang".into(),
+            locations: vec![BoundingBox::new(-122.75, 36.8, -121.75, 37.8)].into(),
+            count: Some(-100),
+            tweet_mode: Some(TweetMode::Extended),
+            ..Parameters::default()
+        };
+        assert_eq!(
+            oauth.post(FILTER, &sorted_pairs(&parameters, "")),
+            oauth.post(FILTER, &parameters),
+        );
+
+        // Custom keys placed before, among and after the `oauth_*` parameters.
+        let parameters = Parameters {
+            track: "rust".into(),
+            custom: vec![
+                ("oauth_d".into(), "x".into()),
+                ("a".into(), "b".into()),
+                ("oauth_zzz".into(), "y".into()),
+                ("oauth_token0".into(), "z".into()),
+            ],
+            ..Parameters::default()
+        };
+        let expected: BTreeSet<_> = vec![
+            ("a", "b"),
+            ("oauth_d", "x"),
+            ("oauth_token0", "z"),
+            ("oauth_zzz", "y"),
+            ("track", "rust"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            oauth.get(SAMPLE, &sorted_pairs(&parameters, "")),
+            oauth.get(SAMPLE, &expected),
+        );
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let token = Token::from_parts("", "", "", "");
+
+        let mut builder = Builder::new(token);
+        builder
+            .track("rust")
+            .parameter("track", "@Twitter")
+            .endpoint((
+                RequestMethod::POST,
+                Uri::from_static("https://stream.twitter.com/1.1/statuses/filter.json?track=go"),
+            ));
+        let req = prepare_request(
+            builder.endpoint.as_ref(),
+            &builder.token,
+            &builder.parameters,
+        );
+        assert_eq!(req.body(), b"track=%40Twitter&track=rust");
+    }
+
+    #[test]
+    fn track_phrases() {
+        let mut builder = Builder::new(Token::from_parts("", "", "", ""));
+        builder.track_phrases(&["@Twitter", "rust lang"]);
+        assert_eq!(builder.parameters.track, "@Twitter,rust lang");
+        builder.track_phrases(&["a,b", "c"]);
+        assert_eq!(builder.parameters.track, "a,b,c");
+        builder.track_phrases(Vec::<String>::new());
+        assert_eq!(builder.parameters.track, "");
+    }
+
+    #[test]
+    fn debug_hides_token() {
+        let token = Token::from_parts(
+            "consumer_key",
+            "consumer_secret",
+            "access_key",
+            "access_secret",
+        );
+        let mut builder = Builder::new(token);
+        builder.track("@Twitter");
+
+        let debug = format!("{:?}", builder);
+        assert!(debug.contains("@Twitter"));
+        for secret in &[
+            "consumer_key",
+            "consumer_secret",
+            "access_key",
+            "access_secret",
+        ] {
+            assert!(!debug.contains(secret), "{:?} contains {:?}", debug, secret);
+        }
+
+        let req = prepare_request(None, &builder.token, &builder.parameters);
+        assert!(req.headers()[AUTHORIZATION].is_sensitive());
+    }
+}
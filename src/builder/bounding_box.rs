@@ -1,6 +1,3 @@
-pub use http::Method as RequestMethod;
-pub use http::Uri;
-
 use std::mem;
 use std::slice;
 
@@ -47,6 +44,34 @@ impl BoundingBox {
         }
     }
 
+    /// Returns `true` if the coordinates describe a bounding box that the Streaming API accepts.
+    ///
+    /// That is, the longitudes are within `-180.0..=180.0`, the latitudes are within
+    /// `-90.0..=90.0`, and the southwest corner is to the south and west of the northeast corner.
+    /// The API rejects a request with an invalid bounding box with HTTP 406, so this is useful for
+    /// checking user-supplied coordinates, which are easily transposed, before connecting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use twitter_stream::builder::BoundingBox;
+    ///
+    /// assert!(BoundingBox::new(-122.75, 36.8, -121.75, 37.8).is_valid());
+    /// // Latitude and longitude swapped.
+    /// assert!(!BoundingBox::new(36.8, -122.75, 37.8, -121.75).is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        let longitude = |x: f64| (-180.0..=180.0).contains(&x);
+        let latitude = |y: f64| (-90.0..=90.0).contains(&y);
+
+        longitude(self.west_longitude)
+            && longitude(self.east_longitude)
+            && latitude(self.south_latitude)
+            && latitude(self.north_latitude)
+            && self.west_longitude <= self.east_longitude
+            && self.south_latitude <= self.north_latitude
+    }
+
     /// Creates a slice of `BoundingBox`-es from a slice of arrays of
     /// `[west_longitude, south_latitude, east_longitude, north_latitude]`.
     ///
@@ -226,6 +251,20 @@ impl From<((f64, f64), (f64, f64))> for BoundingBox {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn is_valid() {
+        assert!(BoundingBox::new(-180.0, -90.0, 180.0, 90.0).is_valid());
+        assert!(BoundingBox::new(-74.0, 40.0, -73.0, 41.0).is_valid());
+        // Corners swapped.
+        assert!(!BoundingBox::new(-73.0, 41.0, -74.0, 40.0).is_valid());
+        // Out of range.
+        assert!(!BoundingBox::new(-122.75, 36.8, 181.0, 37.8).is_valid());
+        assert!(!BoundingBox::new(-122.75, -91.0, -121.75, 37.8).is_valid());
+        assert!(!BoundingBox::new(f64::NAN, 36.8, -121.75, 37.8).is_valid());
+    }
+
     mod soundness {
         use slice_of_array::SliceNestExt;
 
@@ -255,11 +294,11 @@ mod tests {
         fn unflatten_alignment() {
             static ARRAY: [f64; 5] = [1., 2., 3., 4., 5.];
             assert_eq!(
-                BoundingBox::unflatten_slice(&ARRAY[..4].nest()),
+                BoundingBox::unflatten_slice(ARRAY[..4].nest()),
                 [BoundingBox::new(1., 2., 3., 4.)],
             );
             assert_eq!(
-                BoundingBox::unflatten_slice(&ARRAY[1..].nest()),
+                BoundingBox::unflatten_slice(ARRAY[1..].nest()),
                 [BoundingBox::new(2., 3., 4., 5.)],
             );
         }
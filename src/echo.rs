@@ -0,0 +1,58 @@
+//! Helpers for [OAuth Echo].
+//!
+//! OAuth Echo lets a third-party service (like a media upload service) verify the identity of
+//! a Twitter user on behalf of your application. Your application signs a request to
+//! `account/verify_credentials` and hands the result to the service in the headers produced by
+//! [`headers`], which the service then uses to call the Twitter API itself.
+//!
+//! [OAuth Echo]: https://developer.twitter.com/en/docs/authentication/oauth-echo
+
+use http::header::{HeaderMap, HeaderValue};
+
+use crate::Token;
+
+/// The `X-Auth-Service-Provider` header, containing the URI the service should use to verify
+/// the credentials.
+pub const X_AUTH_SERVICE_PROVIDER: &str = "x-auth-service-provider";
+
+/// The `X-Verify-Credentials-Authorization` header, containing the `Authorization` header value
+/// the service should send to [`X_AUTH_SERVICE_PROVIDER`].
+pub const X_VERIFY_CREDENTIALS_AUTHORIZATION: &str = "x-verify-credentials-authorization";
+
+const VERIFY_CREDENTIALS: &str = "https://api.twitter.com/1.1/account/verify_credentials.json";
+
+/// Creates the OAuth Echo headers for delegating the verification of `token` to another service.
+///
+/// # Example
+///
+/// ```
+/// use twitter_stream::echo;
+/// use twitter_stream::Token;
+///
+/// let token = Token::from_parts("consumer_key", "consumer_secret", "access_key", "access_secret");
+/// let headers = echo::headers(&token);
+///
+/// assert_eq!(
+///     headers[echo::X_AUTH_SERVICE_PROVIDER],
+///     "https://api.twitter.com/1.1/account/verify_credentials.json",
+/// );
+/// assert!(headers[echo::X_VERIFY_CREDENTIALS_AUTHORIZATION].is_sensitive());
+/// ```
+pub fn headers<C, A>(token: &Token<C, A>) -> HeaderMap
+where
+    C: AsRef<str>,
+    A: AsRef<str>,
+{
+    let authorization = oauth::get(VERIFY_CREDENTIALS, &(), &token.as_ref(), oauth::HmacSha1);
+    let mut authorization = HeaderValue::from_str(&authorization).unwrap();
+    authorization.set_sensitive(true);
+
+    let mut headers = HeaderMap::with_capacity(2);
+    headers.insert(
+        X_AUTH_SERVICE_PROVIDER,
+        HeaderValue::from_static(VERIFY_CREDENTIALS),
+    );
+    headers.insert(X_VERIFY_CREDENTIALS_AUTHORIZATION, authorization);
+
+    headers
+}
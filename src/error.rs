@@ -34,9 +34,56 @@ impl<E: Display> Display for Error<E> {
         use crate::Error::*;
 
         match *self {
-            Http(ref code) => write!(f, "HTTP status code: {}", code),
+            Http(ref code) => {
+                write!(f, "HTTP status code: {}", code)?;
+                if let Some(cause) = likely_cause(code) {
+                    write!(f, " ({})", cause)?;
+                }
+                Ok(())
+            }
             Service(ref e) => write!(f, "HTTP client error: {}", e),
             Utf8(ref e) => Display::fmt(e, f),
         }
     }
 }
+
+/// Returns the most likely cause of an HTTP error from the Streaming API.
+///
+/// See the [Twitter Developer Documentation][1] for the meanings of the status codes.
+///
+/// [1]: https://developer.twitter.com/en/docs/tweets/filter-realtime/guides/connecting#http-error-codes
+fn likely_cause(code: &StatusCode) -> Option<&'static str> {
+    let cause = match code.as_u16() {
+        401 => "the credentials are invalid or the OAuth signature was rejected",
+        403 => "the account is not permitted to access the endpoint",
+        404 => "the endpoint does not exist",
+        406 => {
+            "a parameter is invalid, e.g., a malformed `track` phrase or bounding box, \
+             an invalid user ID, or no filter predicate at all"
+        }
+        413 => "a parameter list like `track`, `follow` or `locations` exceeds the access level",
+        416 => "the `count` parameter is out of range or not permitted for the account",
+        420 => "the client is connecting too frequently and should back off before reconnecting",
+        503 => "the streaming server is overloaded and the client should retry later",
+        _ => return None,
+    };
+    Some(cause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_http() {
+        let e: Error = Error::Http(StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            e.to_string(),
+            "HTTP status code: 401 Unauthorized \
+             (the credentials are invalid or the OAuth signature was rejected)",
+        );
+
+        let e: Error = Error::Http(StatusCode::IM_A_TEAPOT);
+        assert_eq!(e.to_string(), "HTTP status code: 418 I'm a teapot");
+    }
+}
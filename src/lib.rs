@@ -94,7 +94,9 @@ messages.
 #[macro_use]
 mod util;
 
+pub mod auth;
 pub mod builder;
+pub mod echo;
 pub mod error;
 #[cfg(feature = "hyper")]
 #[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
@@ -134,6 +136,7 @@ pin_project! {
     pub struct TwitterStream<B> {
         #[pin]
         inner: Lines<B>,
+        parts: http::response::Parts,
     }
 }
 
@@ -143,13 +146,35 @@ pub type Token<C = String, T = String> = oauth_credentials::Token<C, T>;
 
 impl<B: Body> TwitterStream<B> {
     /// Creates a `Builder` for `TwitterStream`.
-    pub fn builder<'a, C, A>(token: Token<C, A>) -> Builder<'a, Token<C, A>>
-    where
-        C: AsRef<str>,
-        A: AsRef<str>,
-    {
+    pub fn builder<'a, T: auth::Authenticator>(token: T) -> Builder<'a, T> {
         Builder::new(token)
     }
+
+    /// Returns the status line and headers of the HTTP response the stream was started with.
+    ///
+    /// This can be used to inspect the response before reading any message from the stream,
+    /// e.g., to record the response metadata or to check the `Content-Encoding` header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use http::header::CONTENT_ENCODING;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let token = twitter_stream::Token::from_parts("", "", "", "");
+    /// let stream = twitter_stream::TwitterStream::track("@Twitter", &token)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// if let Some(encoding) = stream.response_parts().headers.get(CONTENT_ENCODING) {
+    ///     println!("Content-Encoding: {:?}", encoding);
+    /// }
+    /// # }
+    /// ```
+    pub fn response_parts(&self) -> &http::response::Parts {
+        &self.parts
+    }
 }
 
 #[cfg(feature = "hyper")]
@@ -238,9 +263,10 @@ where
             return Poll::Ready(Err(Error::Http(res.status())));
         }
 
-        let inner = Lines::new(res.into_body());
+        let (parts, body) = res.into_parts();
+        let inner = Lines::new(body);
 
-        Poll::Ready(Ok(TwitterStream { inner }))
+        Poll::Ready(Ok(TwitterStream { inner, parts }))
     }
 }
 
@@ -253,7 +279,7 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
-        loop {
+        for _ in 0..MAX_BLANK_LINES_PER_POLL {
             let line = match ready!(this.inner.as_mut().poll_next(cx)?) {
                 Some(t) => t,
                 None => return std::task::Poll::Ready(None),
@@ -273,10 +299,51 @@ where
             };
             return Poll::Ready(Some(Ok(line)));
         }
+
+        // Yield to the executor so that a flood of blank lines cannot monopolize the task.
+        cx.waker().wake_by_ref();
+        Poll::Pending
     }
 }
 
+/// Maximum number of blank lines to skip in a single `TwitterStream::poll_next` call.
+const MAX_BLANK_LINES_PER_POLL: usize = 64;
+
 fn is_json_whitespace(c: u8) -> bool {
     // RFC7159 §2
     b" \t\n\r".contains(&c)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures::task;
+
+    use crate::util::test::WakeCount;
+
+    #[test]
+    fn blank_lines_yield() {
+        let body = "\r\n".repeat(MAX_BLANK_LINES_PER_POLL + 1) + "{}\r\n";
+        let (parts, ()) = Response::new(()).into_parts();
+        let mut stream = TwitterStream {
+            inner: Lines::new(http_body::Full::new(Bytes::from(body))),
+            parts,
+        };
+
+        let count = Arc::new(WakeCount(AtomicUsize::new(0)));
+        let waker = task::waker(count.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(Ok(line))) => assert_eq!(&*line, "{}"),
+            _ => panic!("expected a message"),
+        }
+    }
+}
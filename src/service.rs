@@ -1,4 +1,38 @@
 //! A trait alias for [`Service`](tower_service::Service).
+//!
+//! Since [`Builder::listen_with_client`](crate::Builder::listen_with_client) accepts any `Service`
+//! that sends HTTP requests, policies like timeouts can be applied by wrapping the HTTP client
+//! with middleware from the [`tower`] ecosystem.
+//!
+//! `listen_with_client` calls the service without calling `poll_ready` first, so the service
+//! must be driven to readiness beforehand, like with [`ServiceExt::ready`]. Some middleware,
+//! like concurrency limits and rate limits, panics otherwise.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use tower::{ServiceBuilder, ServiceExt};
+//! # use hyper_pkg as hyper;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! # let token = twitter_stream::Token::from_parts("", "", "", "");
+//! let conn = hyper_tls::HttpsConnector::new();
+//! let client = hyper::Client::builder().build::<_, hyper::Body>(conn);
+//! let mut client = ServiceBuilder::new()
+//!     .timeout(Duration::from_secs(30))
+//!     .service(client);
+//!
+//! let stream = twitter_stream::Builder::new(token)
+//!     .track("@Twitter")
+//!     .listen_with_client(client.ready().await.unwrap())
+//!     .await
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! [`ServiceExt::ready`]: https://docs.rs/tower/0.4/tower/trait.ServiceExt.html#method.ready
+//! [`tower`]: https://docs.rs/tower
 
 use http::{Request, Response};
 use http_body::Body;
@@ -1,9 +1,9 @@
 use std::fmt::{self, Display, Formatter};
-use std::mem;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use bytes::{Buf, Bytes};
+use bytes::{BufMut, Bytes, BytesMut};
 use futures_core::{ready, Stream};
 use http_body::Body;
 use pin_project_lite::pin_project;
@@ -44,19 +44,28 @@ pin_project! {
         #[pin]
         body: B,
         body_done: bool,
-        buf: Bytes,
+        buf: BytesMut,
+        // Length of the leading part of `buf` that is known not to contain a CRLF.
+        scanned: usize,
     }
 }
 
+/// Maximum number of body chunks to read in a single `Lines::poll_next` call.
+///
+/// This prevents a body that is split into many tiny chunks from monopolizing the task.
+const MAX_CHUNKS_PER_POLL: usize = 64;
+
 impl<B: Body> Lines<B> {
     pub fn new(body: B) -> Self {
         Lines {
             body,
             body_done: false,
-            buf: Bytes::new(),
+            buf: BytesMut::new(),
+            scanned: 0,
         }
     }
 
+    #[allow(clippy::type_complexity)]
     fn poll_body(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -77,62 +86,55 @@ impl<B: Body> Stream for Lines<B> {
     type Item = Result<Bytes, Error<B::Error>>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        if let Some(line) = remove_first_line(self.as_mut().project().buf) {
-            return Poll::Ready(Some(Ok(line)));
-        }
-
-        // Now `self.buf` does not have a CRLF.
-        // Extend the buffer until a CRLF is found.
-
-        loop {
-            let mut chunk = loop {
-                if let Some(c) = ready!(self.as_mut().poll_body(cx)?) {
-                    if c.has_remaining() {
-                        break c;
-                    }
-                } else if self.buf.is_empty() {
-                    return Poll::Ready(None);
-                } else {
-                    // `self.buf` does not have CRLF so it is safe to return its content as-is.
-                    let ret = mem::take(self.as_mut().project().buf);
-                    return Poll::Ready(Some(Ok(ret)));
-                }
-            };
-
+        for _ in 0..MAX_CHUNKS_PER_POLL {
             let this = self.as_mut().project();
+            if let Some(line) = remove_first_line(this.buf, *this.scanned) {
+                *this.scanned = 0;
+                return Poll::Ready(Some(Ok(line)));
+            }
+            *this.scanned = this.buf.len();
 
-            if chunk.chunk()[0] == b'\n' && this.buf.last() == Some(&b'\r') {
-                // Drop the CRLF
-                this.buf.truncate(this.buf.len() - 1);
-                chunk.advance(1);
+            // Now `self.buf` does not have a CRLF.
+            // Extend the buffer until a CRLF is found.
 
-                let chunk = chunk.copy_to_bytes(chunk.remaining());
-                return Poll::Ready(Some(Ok(mem::replace(this.buf, chunk))));
+            if let Some(chunk) = ready!(self.as_mut().poll_body(cx)?) {
+                self.as_mut().project().buf.put(chunk);
+            } else if self.buf.is_empty() {
+                return Poll::Ready(None);
             } else {
-                let mut chunk = chunk.copy_to_bytes(chunk.remaining());
-                if let Some(line) = remove_first_line(&mut chunk) {
-                    let ret = if this.buf.is_empty() {
-                        line
-                    } else {
-                        let mut ret = Vec::with_capacity(this.buf.len() + line.len());
-                        ret.extend_from_slice(&this.buf);
-                        ret.extend_from_slice(&line);
-                        ret.into()
-                    };
-                    *this.buf = chunk;
-                    return Poll::Ready(Some(Ok(ret)));
-                } else {
-                    *this.buf = if this.buf.is_empty() {
-                        chunk
-                    } else {
-                        let mut buf = Vec::with_capacity(this.buf.len() + chunk.len());
-                        buf.extend_from_slice(&this.buf);
-                        buf.extend_from_slice(&chunk);
-                        buf.into()
-                    }
-                }
+                // `self.buf` does not have CRLF so it is safe to return its content as-is.
+                let this = self.as_mut().project();
+                *this.scanned = 0;
+                return Poll::Ready(Some(Ok(this.buf.split().freeze())));
             }
         }
+
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// A placeholder for credentials in `Debug` output.
+pub struct Hidden;
+
+impl fmt::Debug for Hidden {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("<hidden>")
+    }
+}
+
+/// A future that resolves with the output of a closure, like `futures::future::poll_fn`.
+pub struct PollFn<F>(F);
+
+pub fn poll_fn<T, F: FnMut(&mut Context<'_>) -> Poll<T> + Unpin>(f: F) -> PollFn<F> {
+    PollFn(f)
+}
+
+impl<T, F: FnMut(&mut Context<'_>) -> Poll<T> + Unpin> Future for PollFn<F> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        (self.0)(cx)
     }
 }
 
@@ -147,28 +149,94 @@ pub fn fmt_join<T: Display>(t: &[T], sep: &str, f: &mut Formatter<'_>) -> fmt::R
     Ok(())
 }
 
-fn remove_first_line(buf: &mut Bytes) -> Option<Bytes> {
-    if buf.len() < 2 {
-        return None;
+/// Percent-encodes `s` as described in [RFC 5849 section 3.6][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc5849#section-3.6
+pub fn percent_encode(s: impl AsRef<[u8]>) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let s = s.as_ref();
+    let mut ret = String::with_capacity(s.len());
+    for &b in s {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            ret.push(b as char);
+        } else {
+            ret.push('%');
+            ret.push(HEX[usize::from(b >> 4)] as char);
+            ret.push(HEX[usize::from(b & 0xF)] as char);
+        }
+    }
+    ret
+}
+
+/// Decodes a component of a URI query, treating `+` as a space.
+///
+/// A `%` that does not begin a valid escape sequence is left as is.
+pub fn percent_decode(s: &str) -> Vec<u8> {
+    fn hex(b: u8) -> Option<u8> {
+        (b as char).to_digit(16).map(|d| d as u8)
+    }
+
+    let s = s.as_bytes();
+    let mut ret = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        let b = match s[i] {
+            b'+' => b' ',
+            b'%' if i + 2 < s.len() => {
+                if let (Some(h), Some(l)) = (hex(s[i + 1]), hex(s[i + 2])) {
+                    i += 2;
+                    h << 4 | l
+                } else {
+                    b'%'
+                }
+            }
+            b => b,
+        };
+        ret.push(b);
+        i += 1;
     }
+    ret
+}
 
-    if let Some(i) = memchr::memchr(b'\n', &buf[1..]) {
-        if buf[i] == b'\r' {
-            let mut line = buf.split_to(i + 2);
-            line.truncate(i); // Drop the CRLF
-            return Some(line);
+/// Removes the first line terminated by a CRLF from `buf`, looking for the CRLF in `buf[from..]`.
+///
+/// `buf[..from]` must not contain a CRLF.
+fn remove_first_line(buf: &mut BytesMut, from: usize) -> Option<Bytes> {
+    let mut from = from;
+    while let Some(i) = memchr::memchr(b'\n', &buf[from..]) {
+        let lf = from + i;
+        if lf > 0 && buf[lf - 1] == b'\r' {
+            let mut line = buf.split_to(lf + 1);
+            line.truncate(lf - 1); // Drop the CRLF
+            return Some(line.freeze());
         }
+        from = lf + 1;
     }
 
     None
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
-    use bytes::Bytes;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use bytes::{Buf, Bytes};
     use futures::executor::block_on_stream;
     use futures::stream::{self, StreamExt, TryStream};
+    use futures::task::{self, ArcWake};
+
+    /// A waker that counts how many times it has been woken.
+    pub(crate) struct WakeCount(pub AtomicUsize);
+
+    impl ArcWake for WakeCount {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
 
     pin_project! {
             struct StreamBody<S> {
@@ -225,4 +293,66 @@ mod test {
 
         assert_eq!(lines.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
     }
+
+    #[test]
+    fn bare_lf() {
+        let body = ["a\nb\r\nc", "\nd\r", "\n"];
+
+        let lines = Lines::new(StreamBody {
+            stream: stream::iter(&body).map(|&c| Ok(Bytes::from_static(c.as_bytes()))),
+        });
+        let lines = block_on_stream(lines)
+            .map(|s: Result<_, Error>| String::from_utf8(s.unwrap().to_vec()).unwrap());
+
+        assert_eq!(lines.collect::<Vec<_>>(), ["a\nb", "c\nd"]);
+    }
+
+    #[test]
+    fn many_chunks_yield() {
+        let body = std::iter::repeat("a")
+            .take(MAX_CHUNKS_PER_POLL + 1)
+            .chain(Some("\r\n"));
+        let mut lines = Lines::new(StreamBody {
+            stream: stream::iter(body).map(|c| Ok::<_, Error>(Bytes::from_static(c.as_bytes()))),
+        });
+
+        let count = Arc::new(WakeCount(AtomicUsize::new(0)));
+        let waker = task::waker(count.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut lines).poll_next(&mut cx).is_pending());
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+
+        match Pin::new(&mut lines).poll_next(&mut cx) {
+            Poll::Ready(Some(Ok(line))) => {
+                assert_eq!(line, "a".repeat(MAX_CHUNKS_PER_POLL + 1).as_bytes())
+            }
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn percent_encode() {
+        assert_eq!(
+            super::percent_encode("Ladies + Gentlemen"),
+            "Ladies%20%2B%20Gentlemen"
+        );
+        assert_eq!(
+            super::percent_encode("An encoded string!"),
+            "An%20encoded%20string%21"
+        );
+        assert_eq!(
+            super::percent_encode("Dogs, Cats & Mice"),
+            "Dogs%2C%20Cats%20%26%20Mice"
+        );
+        assert_eq!(super::percent_encode("-._~☃"), "-._~%E2%98%83");
+    }
+
+    #[test]
+    fn percent_decode() {
+        assert_eq!(super::percent_decode("a+b%2c%2C"), b"a b,,");
+        assert_eq!(super::percent_decode("%E2%98%83"), "☃".as_bytes());
+        assert_eq!(super::percent_decode("100%"), b"100%");
+        assert_eq!(super::percent_decode("%zz%4"), b"%zz%4");
+    }
 }